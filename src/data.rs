@@ -0,0 +1,751 @@
+use anyhow::{bail, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use crate::query::Query;
+use crate::repository::{Repository, TomlRepository};
+
+/// A Project holds multiple tasks. It also holds the location of
+/// the file these tasks were loaded from and how many
+/// recursive steps have been taken to reach that file.
+pub struct Project {
+    pub path: PathBuf,
+    pub steps: usize,
+    pub data: ProjectData,
+    repo: Box<dyn Repository>,
+}
+
+impl Project {
+    /// Creates a new project with no tasks, persisted via a [`TomlRepository`].
+    pub fn new(project_file: PathBuf, steps: usize, name: impl ToString) -> Self {
+        Self::with_repository(
+            project_file.clone(),
+            steps,
+            name,
+            Box::new(TomlRepository::new(project_file)),
+        )
+    }
+
+    /// Creates a new project with no tasks, persisted through the given repository.
+    pub fn with_repository(
+        project_file: PathBuf,
+        steps: usize,
+        name: impl ToString,
+        repo: Box<dyn Repository>,
+    ) -> Self {
+        Self {
+            path: project_file,
+            data: ProjectData {
+                name: name.to_string(),
+                next_id: 0,
+                tasks: Vec::new(),
+            },
+            steps,
+            repo,
+        }
+    }
+
+    /// Tries to load a project from the specified file using the default
+    /// TOML-backed repository.
+    ///
+    /// # Errors
+    /// This function will return an Error when the file doesn't exists, or
+    /// a Project couldn't be loaded from it.
+    pub fn load(project_file: PathBuf, steps: usize) -> Result<Self> {
+        Self::load_with_repository(
+            project_file.clone(),
+            steps,
+            Box::new(TomlRepository::new(project_file)),
+        )
+    }
+
+    /// Tries to load a project through an arbitrary repository.
+    ///
+    /// # Errors
+    /// This function will return an Error when the repository fails to
+    /// load a `ProjectData` from its backing store.
+    pub fn load_with_repository(
+        project_file: PathBuf,
+        steps: usize,
+        mut repo: Box<dyn Repository>,
+    ) -> Result<Self> {
+        let mut data = repo.load()?;
+
+        // Older project files were written before `created` existed; fill
+        // it in now so callers always see a timestamp. This isn't
+        // persisted until the next explicit `save`.
+        for task in &mut data.tasks {
+            if task.created.is_empty() {
+                task.created = Utc::now().to_rfc3339();
+            }
+        }
+
+        // Older project files were written before `next_id` existed, or
+        // may simply be stale; make sure it can never hand out an index
+        // that's already in use.
+        let min_next_id = data.tasks.iter().map(|t| t.index + 1).max().unwrap_or(0);
+        data.next_id = data.next_id.max(min_next_id);
+
+        Ok(Self {
+            path: project_file,
+            data,
+            steps,
+            repo,
+        })
+    }
+
+    /// Saves the project to where it was loaded from.
+    ///
+    /// # Errors
+    /// This function will return an Error when the file this project was
+    /// loaded from can't be written(doesnt exist, permission denied) or the
+    /// project could not be serialized. Both of these are not very likely to occur
+    pub fn save(&mut self) -> Result<()> {
+        self.repo.save(&self.data)
+    }
+
+    /// Returns a mutable reference to a contained Task.
+    ///
+    /// # Errors
+    /// This function will return an error if no Task with the given index
+    /// could be found.
+    pub fn get_task_mut(&mut self, index: usize) -> Result<&mut Task> {
+        for t in &mut self.data.tasks {
+            if t.index == index {
+                return Ok(t);
+            }
+        }
+        bail!("no task with index {}", &index)
+    }
+
+    pub fn add(&mut self, name: impl ToString, completed: bool) {
+        let task = Task::new(name.to_string(), completed, self.take_next_id());
+        self.data.tasks.push(task);
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        self.data.tasks.retain(|t| t.index != index);
+    }
+
+    pub fn remove_all(&mut self) {
+        self.data.tasks.clear();
+    }
+
+    pub fn remove_completed(&mut self) {
+        self.data.tasks.retain(|t| !t.completed);
+    }
+
+    pub fn mark_completion_all(&mut self, completed: bool) {
+        for t in &mut self.data.tasks {
+            t.completed = completed;
+        }
+    }
+
+    /// Marks the Task with the given Index as completed/not completed.
+    ///
+    /// Completing a task whose dependencies are still open is refused
+    /// unless `force` is set, in which case the task is completed
+    /// regardless of its dependency state.
+    ///
+    /// # Errors
+    /// This function will return an error if a Task with the given index
+    /// could not be found, or if `completed` is true while any of its
+    /// dependencies are still open and `force` is false.
+    pub fn mark_completion(&mut self, index: usize, completed: bool, force: bool) -> Result<()> {
+        if completed && !force {
+            let blocking = self.blocking_dependencies(index);
+            if !blocking.is_empty() {
+                bail!(
+                    "task {} is blocked by incomplete dependencies: {:?}",
+                    index,
+                    blocking
+                );
+            }
+        }
+
+        let task = self.get_task_mut(index)?;
+        task.completed = completed;
+        Ok(())
+    }
+
+    /// Adds a dependency from `task` on `dep`, meaning `task` cannot be
+    /// completed until `dep` is. Rejects the edge if it would introduce a
+    /// cycle into the dependency graph.
+    ///
+    /// # Errors
+    /// Returns an error if `dep` doesn't exist or if adding the edge would
+    /// create a cycle.
+    pub fn add_dependency(&mut self, task: usize, dep: usize) -> Result<()> {
+        if !self.data.tasks.iter().any(|t| t.index == dep) {
+            bail!("no task with index {}", dep);
+        }
+
+        {
+            let t = self.get_task_mut(task)?;
+            if t.dependencies.contains(&dep) {
+                return Ok(());
+            }
+            t.dependencies.push(dep);
+        }
+
+        if self.topo_order().is_err() {
+            if let Ok(t) = self.get_task_mut(task) {
+                t.dependencies.retain(|&d| d != dep);
+            }
+            bail!("adding dependency {} -> {} would create a cycle", task, dep);
+        }
+
+        Ok(())
+    }
+
+    /// Returns every incomplete task whose dependencies are all completed.
+    pub fn ready_tasks(&self) -> Vec<&Task> {
+        self.data
+            .tasks
+            .iter()
+            .filter(|t| !t.completed && self.blocking_dependencies(t.index).is_empty())
+            .collect()
+    }
+
+    /// Sorts the project's tasks in place by priority (highest first), with
+    /// ties broken by index.
+    pub fn sort_by_priority(&mut self) {
+        self.data
+            .tasks
+            .sort_by(|a, b| b.priority.cmp(&a.priority).then(a.index.cmp(&b.index)));
+    }
+
+    /// Returns the tasks ordered by priority (highest first) without
+    /// disturbing the project's own task ordering.
+    pub fn tasks_ordered(&self) -> Vec<&Task> {
+        let mut tasks: Vec<&Task> = self.data.tasks.iter().collect();
+        tasks.sort_by(|a, b| b.priority.cmp(&a.priority).then(a.index.cmp(&b.index)));
+        tasks
+    }
+
+    /// Adds a tag to the given task, normalizing it to lowercase and
+    /// skipping it if the task is already tagged with it.
+    ///
+    /// # Errors
+    /// Returns an error if no task with the given index exists.
+    pub fn add_tag(&mut self, index: usize, tag: impl Into<String>) -> Result<()> {
+        let tag = tag.into().to_lowercase();
+        let task = self.get_task_mut(index)?;
+        if !task.tags.contains(&tag) {
+            task.tags.push(tag);
+        }
+        Ok(())
+    }
+
+    /// Returns every task matching `query`.
+    pub fn filter(&self, query: &Query) -> Vec<&Task> {
+        self.data.tasks.iter().filter(|t| query.matches(t)).collect()
+    }
+
+    /// Logs a work session against the given task. `minutes` over 59 rolls
+    /// up into whole hours before being stored.
+    ///
+    /// # Errors
+    /// Returns an error if no task with the given index exists.
+    pub fn log_time(&mut self, index: usize, hours: u32, minutes: u32) -> Result<()> {
+        let task = self.get_task_mut(index)?;
+        task.time_entries.push(TimeEntry::new(hours, minutes));
+        Ok(())
+    }
+
+    /// Total time logged across every task in the project, in minutes.
+    pub fn total_minutes(&self) -> u64 {
+        self.data.tasks.iter().map(Task::total_minutes).sum()
+    }
+
+    /// Adds a new task with a due date.
+    pub fn add_with_due(&mut self, name: impl ToString, completed: bool, due: DateTime<Utc>) {
+        let mut task = Task::new(name.to_string(), completed, self.take_next_id());
+        task.due = Some(due.to_rfc3339());
+        self.data.tasks.push(task);
+    }
+
+    /// Returns every incomplete task whose due date is before `now`.
+    pub fn overdue_tasks(&self, now: DateTime<Utc>) -> Vec<&Task> {
+        self.data
+            .tasks
+            .iter()
+            .filter(|t| !t.completed && t.is_overdue(now))
+            .collect()
+    }
+
+    /// Returns the indices of `index`'s dependencies that are not yet completed.
+    fn blocking_dependencies(&self, index: usize) -> Vec<usize> {
+        self.data
+            .tasks
+            .iter()
+            .find(|t| t.index == index)
+            .map(|t| {
+                t.dependencies
+                    .iter()
+                    .copied()
+                    .filter(|dep| {
+                        self.data
+                            .tasks
+                            .iter()
+                            .find(|t| t.index == *dep)
+                            .is_some_and(|t| !t.completed)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Computes a valid completion order for every task via a depth-first
+    /// topological sort over the dependency graph.
+    ///
+    /// # Errors
+    /// Returns an error if the dependency graph contains a cycle.
+    fn topo_order(&self) -> Result<Vec<usize>> {
+        let mut colors: HashMap<usize, Color> = self
+            .data
+            .tasks
+            .iter()
+            .map(|t| (t.index, Color::White))
+            .collect();
+        let mut order = Vec::with_capacity(self.data.tasks.len());
+
+        for t in &self.data.tasks {
+            if colors.get(&t.index) == Some(&Color::White) {
+                self.visit(t.index, &mut colors, &mut order)?;
+            }
+        }
+
+        Ok(order)
+    }
+
+    /// DFS step of [`Self::topo_order`]. A Gray node hit during the walk is
+    /// a back-edge, i.e. a cycle; nodes are emitted in post-order.
+    fn visit(&self, index: usize, colors: &mut HashMap<usize, Color>, order: &mut Vec<usize>) -> Result<()> {
+        colors.insert(index, Color::Gray);
+
+        if let Some(task) = self.data.tasks.iter().find(|t| t.index == index) {
+            for &dep in &task.dependencies {
+                match colors.get(&dep) {
+                    Some(Color::Gray) => bail!("dependency cycle detected at task {}", dep),
+                    Some(Color::Black) => {}
+                    Some(Color::White) | None => self.visit(dep, colors, order)?,
+                }
+            }
+        }
+
+        colors.insert(index, Color::Black);
+        order.push(index);
+        Ok(())
+    }
+
+    /// Returns the next id that will be handed out by [`Self::add`],
+    /// without consuming it.
+    pub const fn next_index(&self) -> usize {
+        self.data.next_id
+    }
+
+    /// Hands out the next task id and advances the counter so it's never
+    /// reused, even after the task it was assigned to is removed.
+    const fn take_next_id(&mut self) -> usize {
+        let id = self.data.next_id;
+        self.data.next_id += 1;
+        id
+    }
+}
+
+/// The part of a Project that needs to be saved/loaded
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProjectData {
+    pub name: String,
+    /// The next id [`Project::add`] will hand out. Monotonically
+    /// increasing: once assigned, an id is never reused, even if its task
+    /// is later removed.
+    #[serde(default)]
+    pub next_id: usize,
+    pub tasks: Vec<Task>,
+}
+
+/// A completable Task within a Project
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Task {
+    pub desc: String,
+    pub index: usize,
+    pub completed: bool,
+    /// Indices of tasks that must be completed before this one.
+    #[serde(default)]
+    pub dependencies: Vec<usize>,
+    #[serde(default)]
+    pub priority: Priority,
+    /// Lowercase, deduplicated labels used to categorize the task.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Logged work sessions, serialized as an array of tables.
+    #[serde(default)]
+    pub time_entries: Vec<TimeEntry>,
+    /// When the task was created, as an RFC3339 timestamp. Empty for tasks
+    /// loaded from a file written before this field existed; backfilled at
+    /// load time.
+    #[serde(default)]
+    pub created: String,
+    /// Optional due date, as an RFC3339 timestamp.
+    #[serde(default)]
+    pub due: Option<String>,
+}
+
+impl Task {
+    pub fn new(name: impl Into<String>, completed: bool, index: usize) -> Self {
+        Self {
+            desc: name.into(),
+            completed,
+            index,
+            dependencies: Vec::new(),
+            priority: Priority::default(),
+            tags: Vec::new(),
+            time_entries: Vec::new(),
+            created: Utc::now().to_rfc3339(),
+            due: None,
+        }
+    }
+
+    /// Total time logged against this task, in minutes.
+    pub fn total_minutes(&self) -> u64 {
+        self.time_entries
+            .iter()
+            .map(|e| u64::from(e.hours) * 60 + u64::from(e.minutes))
+            .sum()
+    }
+
+    /// Whether this task's due date is before `now`.
+    pub fn is_overdue(&self, now: DateTime<Utc>) -> bool {
+        self.due
+            .as_deref()
+            .and_then(|d| DateTime::parse_from_rfc3339(d).ok())
+            .is_some_and(|due| due.with_timezone(&Utc) < now)
+    }
+}
+
+/// A single logged work session. `minutes` is always normalized to `0..60`;
+/// overflow rolls up into `hours` (e.g. 90 minutes becomes 1h30m). This holds
+/// for entries built with [`TimeEntry::new`] and for entries deserialized
+/// from a project file (see `de.rs`) or loaded by the SQLite backend, so the
+/// TOML and SQLite backends agree on the same data. The fields are public, so
+/// an entry built as a struct literal is not normalized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct TimeEntry {
+    pub hours: u32,
+    pub minutes: u8,
+}
+
+impl TimeEntry {
+    pub const fn new(hours: u32, minutes: u32) -> Self {
+        Self {
+            hours: hours + minutes / 60,
+            minutes: (minutes % 60) as u8,
+        }
+    }
+}
+
+/// How urgently a task should be worked on. Serializes as a lowercase
+/// string (see `ser.rs`/`de.rs`); a missing field deserializes to
+/// [`Priority::Unspecified`] so older project files keep loading.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Priority {
+    #[default]
+    Unspecified,
+    Low,
+    Medium,
+    High,
+}
+
+impl Priority {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Unspecified => "unspecified",
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+        }
+    }
+
+    /// Parses a priority label as produced by [`Self::as_str`], falling
+    /// back to [`Self::Unspecified`] for anything unrecognized.
+    pub fn from_label(s: &str) -> Self {
+        match s {
+            "low" => Self::Low,
+            "medium" => Self::Medium,
+            "high" => Self::High,
+            _ => Self::Unspecified,
+        }
+    }
+}
+
+/// The state of a task during a depth-first walk of the dependency graph,
+/// used to distinguish back-edges (cycles) from forward/cross edges.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Color {
+    White,
+    Gray,
+    Black,
+}
+
+#[cfg(test)]
+#[allow(clippy::missing_const_for_fn)]
+mod tests {
+    use std::{io::Write, path::PathBuf};
+    use tempfile::NamedTempFile;
+
+    use super::Project;
+
+    #[test]
+    fn load() {
+        let mut tmpfile = NamedTempFile::new().expect("unable to create tmpfile");
+        write!(
+            tmpfile,
+            r#"
+            name = 'testproject'
+
+            [[tasks]]
+            desc = 'testtask'
+            completed = true
+            index = 67
+
+            [[tasks]]
+            desc = 'moretest'
+            completed = false
+            index = 99
+               "#
+        )
+        .expect("unable to write tmpfile");
+
+        let project =
+            Project::load(tmpfile.path().to_path_buf(), 0).expect("unable to load project");
+
+        assert_eq!(project.data.name, "testproject");
+
+        let summaries: Vec<_> = project
+            .data
+            .tasks
+            .iter()
+            .map(|t| (t.desc.as_str(), t.completed, t.index))
+            .collect();
+        assert_eq!(
+            summaries,
+            vec![("testtask", true, 67), ("moretest", false, 99)]
+        );
+        assert!(
+            project.data.tasks.iter().all(|t| !t.created.is_empty()),
+            "missing `created` should be backfilled at load time"
+        );
+    }
+
+    #[test]
+    fn save() {
+        let tmpfile = NamedTempFile::new().expect("unable to create tmpfile");
+
+        let mut project = Project::new(tmpfile.path().to_path_buf(), 0, "testproject");
+
+        project.add("hypa hypa", false);
+        project.add("HYPA HYPA", true);
+
+        project.save().expect("unable to save project")
+    }
+
+    #[test]
+    fn remove_task() {
+        let mut project = Project::new(PathBuf::from("/invalid/path"), 0, "testproject");
+
+        project.add("iam", false);
+        project.add("root", true);
+
+        project.remove(0);
+
+        assert!(project.get_task_mut(0).is_err());
+        assert_eq!(project.next_index(), 2);
+    }
+
+    #[test]
+    fn remove_all_completed() {
+        let mut project = Project::new(PathBuf::from("/invalid/path"), 0, "testproject");
+
+        project.add("never", true);
+        project.add("gonna", false);
+        project.add("give", false);
+        project.add("you", true);
+        project.add("up", false);
+
+        project.remove_completed();
+
+        assert!(project.get_task_mut(0).is_err());
+        assert!(project.get_task_mut(3).is_err());
+    }
+
+    #[test]
+    fn ids_are_never_reused_after_removal() {
+        let mut project = Project::new(PathBuf::new(), 0, String::from("dummy"));
+        project.add("a", false);
+        project.add("b", false);
+
+        project.remove(1);
+        project.add("c", false);
+
+        let ids: Vec<usize> = project.data.tasks.iter().map(|t| t.index).collect();
+        assert_eq!(ids, vec![0, 2]);
+        assert_eq!(project.next_index(), 3);
+    }
+
+    #[test]
+    fn load_reconciles_next_id_past_existing_indexes() {
+        let mut tmpfile = NamedTempFile::new().expect("unable to create tmpfile");
+        write!(
+            tmpfile,
+            r#"
+            name = 'legacy'
+
+            [[tasks]]
+            desc = 'old task'
+            completed = false
+            index = 999
+               "#
+        )
+        .expect("unable to write tmpfile");
+
+        let mut project =
+            Project::load(tmpfile.path().to_path_buf(), 0).expect("unable to load project");
+
+        assert_eq!(project.next_index(), 1000);
+        project.add("new task", false);
+        assert_eq!(project.get_task_mut(1000).unwrap().desc, "new task");
+    }
+
+    #[test]
+    fn add_dependency_rejects_cycle() {
+        let mut project = Project::new(PathBuf::new(), 0, String::from("dummy"));
+        project.add("a", false);
+        project.add("b", false);
+
+        project.add_dependency(1, 0).expect("dependency should be valid");
+        assert!(project.add_dependency(0, 1).is_err());
+    }
+
+    #[test]
+    fn ready_tasks_respects_dependencies() {
+        let mut project = Project::new(PathBuf::new(), 0, String::from("dummy"));
+        project.add("a", false);
+        project.add("b", false);
+        project.add_dependency(1, 0).expect("dependency should be valid");
+
+        let ready: Vec<usize> = project.ready_tasks().iter().map(|t| t.index).collect();
+        assert_eq!(ready, vec![0]);
+
+        project
+            .mark_completion(0, true, false)
+            .expect("task 0 has no dependencies");
+        let ready: Vec<usize> = project.ready_tasks().iter().map(|t| t.index).collect();
+        assert_eq!(ready, vec![1]);
+    }
+
+    #[test]
+    fn mark_completion_blocked_by_dependency() {
+        let mut project = Project::new(PathBuf::new(), 0, String::from("dummy"));
+        project.add("a", false);
+        project.add("b", false);
+        project.add_dependency(1, 0).expect("dependency should be valid");
+
+        assert!(project.mark_completion(1, true, false).is_err());
+    }
+
+    #[test]
+    fn mark_completion_force_overrides_dependency_block() {
+        let mut project = Project::new(PathBuf::new(), 0, String::from("dummy"));
+        project.add("a", false);
+        project.add("b", false);
+        project.add_dependency(1, 0).expect("dependency should be valid");
+
+        project
+            .mark_completion(1, true, true)
+            .expect("force should override the dependency block");
+        assert!(project.get_task_mut(1).unwrap().completed);
+    }
+
+    #[test]
+    fn tasks_ordered_by_priority_then_index() {
+        use super::Priority;
+
+        let mut project = Project::new(PathBuf::new(), 0, String::from("dummy"));
+        project.add("low prio", false);
+        project.add("high prio", false);
+        project.add("also low prio", false);
+
+        project.get_task_mut(1).unwrap().priority = Priority::High;
+
+        let ordered: Vec<usize> = project.tasks_ordered().iter().map(|t| t.index).collect();
+        assert_eq!(ordered, vec![1, 0, 2]);
+    }
+
+    #[test]
+    fn add_tag_normalizes_and_dedupes() {
+        let mut project = Project::new(PathBuf::new(), 0, String::from("dummy"));
+        project.add("fix the leak", false);
+
+        project.add_tag(0, "Bug").expect("task exists");
+        project.add_tag(0, "BUG").expect("task exists");
+
+        assert_eq!(project.get_task_mut(0).unwrap().tags, vec!["bug"]);
+    }
+
+    #[test]
+    fn filter_combines_predicates() {
+        use crate::query::Query;
+
+        let mut project = Project::new(PathBuf::new(), 0, String::from("dummy"));
+        project.add("fix the leak", false);
+        project.add("write docs", true);
+        project.add_tag(0, "bug").expect("task exists");
+
+        let results = project.filter(&Query::new().has_tag("bug").completed(false));
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].index, 0);
+    }
+
+    #[test]
+    fn log_time_rolls_minutes_into_hours() {
+        let mut project = Project::new(PathBuf::new(), 0, String::from("dummy"));
+        project.add("fix the leak", false);
+
+        project.log_time(0, 0, 90).expect("task exists");
+
+        let task = project.get_task_mut(0).unwrap();
+        assert_eq!(task.time_entries, vec![super::TimeEntry { hours: 1, minutes: 30 }]);
+        assert_eq!(task.total_minutes(), 90);
+    }
+
+    #[test]
+    fn total_minutes_sums_across_tasks() {
+        let mut project = Project::new(PathBuf::new(), 0, String::from("dummy"));
+        project.add("a", false);
+        project.add("b", false);
+
+        project.log_time(0, 1, 0).expect("task exists");
+        project.log_time(1, 0, 30).expect("task exists");
+
+        assert_eq!(project.total_minutes(), 90);
+    }
+
+    #[test]
+    fn overdue_tasks_are_incomplete_and_past_due() {
+        use chrono::{Duration, Utc};
+
+        let mut project = Project::new(PathBuf::new(), 0, String::from("dummy"));
+        let now = Utc::now();
+
+        project.add_with_due("late", false, now - Duration::days(1));
+        project.add_with_due("future", false, now + Duration::days(1));
+        project.add_with_due("late but done", true, now - Duration::days(1));
+
+        let overdue: Vec<usize> = project.overdue_tasks(now).iter().map(|t| t.index).collect();
+        assert_eq!(overdue, vec![0]);
+    }
+}
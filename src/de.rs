@@ -0,0 +1,37 @@
+//! Manual `Deserialize` impls that pair with [`crate::ser`]. Defaults for
+//! fields missing from older project files come from `#[serde(default)]`
+//! on the fields in `data.rs`, not from this module.
+
+use serde::{Deserialize, Deserializer};
+
+use crate::data::{Priority, TimeEntry};
+
+impl<'de> Deserialize<'de> for Priority {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Ok(Self::from_label(&s))
+    }
+}
+
+/// Raw, un-normalized shape of a [`TimeEntry`] as it appears in a project
+/// file. `minutes` is read as a `u32` rather than the narrower `u8`
+/// `TimeEntry` stores it as, so a file written with e.g. `minutes = 90`
+/// still parses; [`TimeEntry::new`] then rolls the overflow into `hours`.
+#[derive(Deserialize)]
+struct RawTimeEntry {
+    hours: u32,
+    minutes: u32,
+}
+
+impl<'de> Deserialize<'de> for TimeEntry {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = RawTimeEntry::deserialize(deserializer)?;
+        Ok(Self::new(raw.hours, raw.minutes))
+    }
+}
@@ -5,20 +5,30 @@
 
 mod data;
 mod de;
+mod query;
+mod repository;
 mod ser;
+#[cfg(feature = "sqlite")]
+mod sqlite_repository;
+mod workspace;
 
 use std::path::{Path, PathBuf};
 
 use anyhow::{bail, Result};
 
 pub use data::{Project, Task};
+pub use query::Query;
+pub use repository::{Repository, TomlRepository};
+#[cfg(feature = "sqlite")]
+pub use sqlite_repository::SqliteRepository;
+pub use workspace::{find_projects, Workspace};
 
 pub const PROJECT_FILE_NAME: &str = ".tutel.toml";
 
 /// Creates a new empty Project in the given directory
 pub fn new_project(dir: &Path, name: String) -> Result<Project> {
     let path = dir.join(PROJECT_FILE_NAME);
-    let mut project = Project::new(path, name);
+    let mut project = Project::new(path, 0, name);
 
     project.save()?;
 
@@ -27,9 +37,9 @@ pub fn new_project(dir: &Path, name: String) -> Result<Project> {
 
 /// Walks the path upwards until .tutel.toml is found and loads it
 pub fn load_project_rec(path: &Path) -> Result<Project> {
-    for p in path.ancestors() {
+    for (steps, p) in path.ancestors().enumerate() {
         if let Some(project_file) = has_project(p) {
-            return Project::load(project_file);
+            return Project::load(project_file, steps);
         }
     }
 
@@ -0,0 +1,81 @@
+use crate::data::Task;
+
+/// A composable filter over a project's tasks, built by chaining predicate
+/// methods and passed to [`crate::data::Project::filter`].
+///
+/// ```ignore
+/// let open_bugs = project.filter(&Query::new().has_tag("bug").completed(false));
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct Query {
+    tag: Option<String>,
+    completed: Option<bool>,
+    desc_contains: Option<String>,
+}
+
+impl Query {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Matches tasks tagged with `tag` (case-insensitive).
+    #[must_use]
+    pub fn has_tag(mut self, tag: impl Into<String>) -> Self {
+        self.tag = Some(tag.into().to_lowercase());
+        self
+    }
+
+    /// Matches tasks whose completion state is exactly `completed`.
+    #[must_use]
+    pub const fn completed(mut self, completed: bool) -> Self {
+        self.completed = Some(completed);
+        self
+    }
+
+    /// Matches tasks whose description contains `substr`.
+    #[must_use]
+    pub fn desc_contains(mut self, substr: impl Into<String>) -> Self {
+        self.desc_contains = Some(substr.into());
+        self
+    }
+
+    pub(crate) fn matches(&self, task: &Task) -> bool {
+        if let Some(tag) = &self.tag {
+            if !task.tags.iter().any(|t| t == tag) {
+                return false;
+            }
+        }
+
+        if let Some(completed) = self.completed {
+            if task.completed != completed {
+                return false;
+            }
+        }
+
+        if let Some(substr) = &self.desc_contains {
+            if !task.desc.contains(substr.as_str()) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Query;
+    use crate::data::Task;
+
+    #[test]
+    fn matches_combined_predicates() {
+        let mut task = Task::new("fix the bug", false, 0);
+        task.tags = vec![String::from("bug")];
+
+        let query = Query::new().has_tag("bug").completed(false).desc_contains("fix");
+        assert!(query.matches(&task));
+
+        task.completed = true;
+        assert!(!query.matches(&task));
+    }
+}
@@ -0,0 +1,54 @@
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+
+use crate::data::ProjectData;
+
+/// Abstracts over where a project's tasks actually live.
+///
+/// `Project` drives all persistence through this trait instead of talking
+/// to `std::fs`/`toml` directly, so the in-memory `ProjectData` view stays
+/// backend-agnostic. Both `load` and `save` always operate on the whole
+/// project: `Project` mutates `ProjectData` in memory and only touches the
+/// backing store on an explicit `Project::save`. [`TomlRepository`] is the
+/// default, file-based backend; the `sqlite` feature adds
+/// [`crate::sqlite_repository::SqliteRepository`], which stores the same
+/// whole-snapshot view in a single-file database instead of a TOML file.
+pub trait Repository {
+    /// Reads the full project (name + tasks) from the backing store.
+    fn load(&mut self) -> Result<ProjectData>;
+
+    /// Persists the full project to the backing store.
+    fn save(&mut self, data: &ProjectData) -> Result<()>;
+}
+
+/// The original backend: a whole `ProjectData` serialized as pretty TOML.
+#[derive(Debug)]
+pub struct TomlRepository {
+    path: PathBuf,
+}
+
+impl TomlRepository {
+    pub const fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub const fn path(&self) -> &PathBuf {
+        &self.path
+    }
+}
+
+impl Repository for TomlRepository {
+    fn load(&mut self) -> Result<ProjectData> {
+        let file_content =
+            fs::read_to_string(&self.path).context("unable to read project file")?;
+
+        toml::from_str(file_content.as_str()).context("invalid project file syntax")
+    }
+
+    fn save(&mut self, data: &ProjectData) -> Result<()> {
+        let serialized = toml::to_string_pretty(data)?;
+        fs::write(&self.path, serialized).context("unable to write project file")
+    }
+}
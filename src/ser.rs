@@ -0,0 +1,16 @@
+//! Manual `Serialize` impls for types whose TOML representation isn't a
+//! straight derive (e.g. enums that should round-trip as a plain lowercase
+//! string rather than an externally-tagged table).
+
+use serde::{Serialize, Serializer};
+
+use crate::data::Priority;
+
+impl Serialize for Priority {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
@@ -0,0 +1,204 @@
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection};
+use std::path::{Path, PathBuf};
+
+use crate::data::{Priority, ProjectData, Task, TimeEntry};
+use crate::repository::Repository;
+
+/// SQLite-backed [`Repository`].
+///
+/// Like [`crate::repository::TomlRepository`], this is a whole-snapshot
+/// backend: `load` reads every row into a `ProjectData` and `save` replaces
+/// every row in one transaction. Tasks are still stored one row per task,
+/// keyed by `idx`, with fields added after the original `desc`/`completed`
+/// pair (priority, tags, dependencies, ...) appended as columns rather than
+/// kept in a single blob, so a future caller querying the database directly
+/// (outside of this crate) can filter in SQL instead of parsing a blob.
+#[derive(Debug)]
+pub struct SqliteRepository {
+    conn: Connection,
+    name_path: PathBuf,
+}
+
+impl SqliteRepository {
+    /// Opens (creating if necessary) a SQLite database at `path` and runs
+    /// any pending migrations.
+    pub fn open(path: &Path) -> Result<Self> {
+        let conn = Connection::open(path).context("unable to open sqlite database")?;
+        let repo = Self {
+            conn,
+            name_path: path.to_path_buf(),
+        };
+        repo.migrate()?;
+        Ok(repo)
+    }
+
+    fn migrate(&self) -> Result<()> {
+        self.conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS project (
+                id INTEGER PRIMARY KEY CHECK (id = 0),
+                name TEXT NOT NULL,
+                next_id INTEGER NOT NULL DEFAULT 0
+            );
+            CREATE TABLE IF NOT EXISTS tasks (
+                idx INTEGER PRIMARY KEY,
+                desc TEXT NOT NULL,
+                completed INTEGER NOT NULL,
+                dependencies TEXT NOT NULL DEFAULT '',
+                priority TEXT NOT NULL DEFAULT 'unspecified',
+                tags TEXT NOT NULL DEFAULT '',
+                time_entries TEXT NOT NULL DEFAULT '',
+                created TEXT NOT NULL DEFAULT '',
+                due TEXT
+            );",
+        )?;
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn row_to_task(
+        idx: i64,
+        desc: String,
+        completed: i64,
+        dependencies: String,
+        priority: String,
+        tags: String,
+        time_entries: String,
+        created: String,
+        due: Option<String>,
+    ) -> Task {
+        let mut task = Task::new(desc, completed != 0, idx as usize);
+        task.dependencies = Self::parse_dependencies(&dependencies);
+        task.priority = Priority::from_label(&priority);
+        task.tags = Self::parse_csv(&tags);
+        task.time_entries = Self::parse_time_entries(&time_entries);
+        if !created.is_empty() {
+            task.created = created;
+        }
+        task.due = due;
+        task
+    }
+
+    fn parse_dependencies(raw: &str) -> Vec<usize> {
+        Self::parse_csv(raw)
+            .iter()
+            .filter_map(|s| s.parse().ok())
+            .collect()
+    }
+
+    fn format_dependencies(dependencies: &[usize]) -> String {
+        dependencies
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    fn parse_csv(raw: &str) -> Vec<String> {
+        raw.split(',')
+            .filter(|s| !s.is_empty())
+            .map(String::from)
+            .collect()
+    }
+
+    fn format_csv(values: &[String]) -> String {
+        values.join(",")
+    }
+
+    /// Parses `"h:m;h:m"` into a list of time entries, skipping malformed
+    /// segments rather than failing the whole load.
+    fn parse_time_entries(raw: &str) -> Vec<TimeEntry> {
+        raw.split(';')
+            .filter(|s| !s.is_empty())
+            .filter_map(|entry| {
+                let (hours, minutes) = entry.split_once(':')?;
+                Some(TimeEntry::new(hours.parse().ok()?, minutes.parse().ok()?))
+            })
+            .collect()
+    }
+
+    fn format_time_entries(entries: &[TimeEntry]) -> String {
+        entries
+            .iter()
+            .map(|e| format!("{}:{}", e.hours, e.minutes))
+            .collect::<Vec<_>>()
+            .join(";")
+    }
+}
+
+impl Repository for SqliteRepository {
+    fn load(&mut self) -> Result<ProjectData> {
+        let name: String = self
+            .conn
+            .query_row("SELECT name FROM project WHERE id = 0", [], |row| {
+                row.get(0)
+            })
+            .unwrap_or_else(|_| {
+                self.name_path
+                    .file_stem()
+                    .map_or_else(|| String::from("project"), |s| s.to_string_lossy().into_owned())
+            });
+
+        let next_id: i64 = self
+            .conn
+            .query_row("SELECT next_id FROM project WHERE id = 0", [], |row| {
+                row.get(0)
+            })
+            .unwrap_or(0);
+
+        let mut stmt = self.conn.prepare(
+            "SELECT idx, desc, completed, dependencies, priority, tags, time_entries, created, due
+             FROM tasks ORDER BY idx",
+        )?;
+        let tasks = stmt
+            .query_map([], |row| {
+                Ok(Self::row_to_task(
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                    row.get(5)?,
+                    row.get(6)?,
+                    row.get(7)?,
+                    row.get(8)?,
+                ))
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(ProjectData {
+            name,
+            next_id: next_id as usize,
+            tasks,
+        })
+    }
+
+    fn save(&mut self, data: &ProjectData) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        tx.execute(
+            "INSERT INTO project (id, name, next_id) VALUES (0, ?1, ?2)
+             ON CONFLICT(id) DO UPDATE SET name = excluded.name, next_id = excluded.next_id",
+            params![data.name, data.next_id as i64],
+        )?;
+        tx.execute("DELETE FROM tasks", [])?;
+        for task in &data.tasks {
+            tx.execute(
+                "INSERT INTO tasks (idx, desc, completed, dependencies, priority, tags, time_entries, created, due)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![
+                    task.index as i64,
+                    task.desc,
+                    task.completed,
+                    Self::format_dependencies(&task.dependencies),
+                    task.priority.as_str(),
+                    Self::format_csv(&task.tags),
+                    Self::format_time_entries(&task.time_entries),
+                    task.created,
+                    task.due
+                ],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+}
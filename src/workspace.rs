@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use crate::data::{Project, Task};
+use crate::PROJECT_FILE_NAME;
+
+/// Recursively scans `root` for project files, up to `max_depth`
+/// directories deep. Directories whose name starts with `.` are skipped
+/// unless `include_hidden` is set.
+pub fn find_projects(root: &Path, max_depth: usize, include_hidden: bool) -> Vec<PathBuf> {
+    let mut found = Vec::new();
+    walk(root, max_depth, include_hidden, &mut found);
+    found
+}
+
+fn walk(dir: &Path, depth_left: usize, include_hidden: bool, found: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+
+        if path.is_file() && path.file_name().and_then(|n| n.to_str()) == Some(PROJECT_FILE_NAME)
+        {
+            found.push(path);
+            continue;
+        }
+
+        if !path.is_dir() || depth_left == 0 {
+            continue;
+        }
+
+        let is_hidden = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.starts_with('.'));
+
+        if is_hidden && !include_hidden {
+            continue;
+        }
+
+        walk(&path, depth_left - 1, include_hidden, found);
+    }
+}
+
+/// Aggregates every `.tutel.toml` project found under a directory tree, so
+/// a user sitting at a repo root can see tasks across every nested
+/// sub-project at once instead of only the closest one.
+pub struct Workspace {
+    projects: Vec<Project>,
+}
+
+impl Workspace {
+    /// Discovers and loads every project under `root`.
+    ///
+    /// # Errors
+    /// Returns an error if any discovered project file fails to load.
+    pub fn discover(root: &Path, max_depth: usize, include_hidden: bool) -> Result<Self> {
+        let projects = find_projects(root, max_depth, include_hidden)
+            .into_iter()
+            .map(|path| Project::load(path, 0))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self { projects })
+    }
+
+    /// Returns every task across every loaded project.
+    pub fn all_tasks(&self) -> Vec<&Task> {
+        self.projects
+            .iter()
+            .flat_map(|p| p.data.tasks.iter())
+            .collect()
+    }
+
+    /// Groups tasks by the path of the project they belong to. Keyed by
+    /// path rather than `p.data.name` since nested projects commonly share a
+    /// default or derived name, which would otherwise collide and silently
+    /// drop one project's tasks from the grouping.
+    pub fn by_project(&self) -> HashMap<&Path, Vec<&Task>> {
+        self.projects
+            .iter()
+            .map(|p| (p.path.as_path(), p.data.tasks.iter().collect()))
+            .collect()
+    }
+
+    /// Returns `(completed, total)` task counts across every project.
+    pub fn completion_stats(&self) -> (usize, usize) {
+        let tasks = self.all_tasks();
+        let completed = tasks.iter().filter(|t| t.completed).count();
+        (completed, tasks.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    use super::{find_projects, Workspace};
+    use crate::new_project;
+
+    #[test]
+    fn find_projects_skips_hidden_dirs_by_default() {
+        let root = tempdir().expect("unable to create tmpdir");
+
+        new_project(root.path(), String::from("top")).expect("unable to create project");
+        fs::create_dir_all(root.path().join("nested")).expect("unable to create dir");
+        new_project(&root.path().join("nested"), String::from("nested")).expect("unable to create project");
+        fs::create_dir_all(root.path().join(".hidden")).expect("unable to create dir");
+        new_project(&root.path().join(".hidden"), String::from("hidden")).expect("unable to create project");
+
+        let found = find_projects(root.path(), 10, false);
+        assert_eq!(found.len(), 2);
+
+        let found_with_hidden = find_projects(root.path(), 10, true);
+        assert_eq!(found_with_hidden.len(), 3);
+    }
+
+    #[test]
+    fn workspace_aggregates_across_projects() {
+        let root = tempdir().expect("unable to create tmpdir");
+
+        let mut top = new_project(root.path(), String::from("top")).expect("unable to create project");
+        top.add("a", true);
+        top.save().expect("unable to save project");
+
+        fs::create_dir_all(root.path().join("nested")).expect("unable to create dir");
+        let mut nested =
+            new_project(&root.path().join("nested"), String::from("nested")).expect("unable to create project");
+        nested.add("b", false);
+        nested.save().expect("unable to save project");
+
+        let workspace = Workspace::discover(root.path(), 10, false).expect("unable to discover projects");
+
+        assert_eq!(workspace.all_tasks().len(), 2);
+        assert_eq!(workspace.completion_stats(), (1, 2));
+        assert_eq!(workspace.by_project().len(), 2);
+    }
+
+    #[test]
+    fn by_project_keeps_projects_with_the_same_name_distinct() {
+        let root = tempdir().expect("unable to create tmpdir");
+
+        let mut top = new_project(root.path(), String::from("dup")).expect("unable to create project");
+        top.add("a", true);
+        top.save().expect("unable to save project");
+
+        fs::create_dir_all(root.path().join("nested")).expect("unable to create dir");
+        let mut nested =
+            new_project(&root.path().join("nested"), String::from("dup")).expect("unable to create project");
+        nested.add("b", false);
+        nested.save().expect("unable to save project");
+
+        let workspace = Workspace::discover(root.path(), 10, false).expect("unable to discover projects");
+
+        assert_eq!(workspace.by_project().len(), 2);
+    }
+}